@@ -0,0 +1,238 @@
+//! Minimal RFC 4180-compliant CSV reader/writer with configurable field
+//! delimiters.
+//!
+//! Unlike a naive `split(',')`/`split('\n')` approach, this module
+//! understands double-quote-wrapped fields, embedded delimiters and
+//! newlines inside quotes, and `""`-style escaped quotes, so criterion
+//! labels, option labels, and comments containing commas or line breaks
+//! round-trip correctly.
+
+const QUOTE: u8 = b'"';
+const CR: u8 = b'\r';
+const LF: u8 = b'\n';
+
+/// Field delimiter used to separate columns in a CSV document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    /// Comma (`,`), the standard RFC 4180 delimiter.
+    Comma,
+    /// Tab (`\t`).
+    Tab,
+    /// Semicolon (`;`).
+    Semicolon,
+    /// Any other single-byte ASCII delimiter.
+    Other(u8),
+}
+
+impl Delimiter {
+    /// Get the byte value of this delimiter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is `Delimiter::Other` wrapping a non-ASCII byte. Such
+    /// a byte could be the leading byte of a multi-byte UTF-8 character in
+    /// the input, which would corrupt parsing, so it's rejected up front
+    /// instead of risking a char-boundary slice panic deep in `parse`.
+    pub fn as_byte(self) -> u8 {
+        match self {
+            Self::Comma => b',',
+            Self::Tab => b'\t',
+            Self::Semicolon => b';',
+            Self::Other(byte) => {
+                assert!(
+                    byte.is_ascii(),
+                    "Delimiter::Other must be an ASCII byte (got {byte:#04x})"
+                );
+                byte
+            }
+        }
+    }
+}
+
+impl Default for Delimiter {
+    fn default() -> Self {
+        Self::Comma
+    }
+}
+
+/// Parse a CSV document into rows of fields, honoring RFC 4180 quoting
+/// rules. A trailing blank line is ignored, matching common CSV producers
+/// that terminate the final row with a newline.
+pub fn parse(csv: &str, delimiter: Delimiter) -> Vec<Vec<String>> {
+    let delimiter = delimiter.as_byte();
+    let bytes = csv.as_bytes();
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut row_started = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let byte = bytes[i];
+
+        if in_quotes {
+            if byte == QUOTE {
+                if bytes.get(i + 1) == Some(&QUOTE) {
+                    field.push('"');
+                    i += 2;
+                } else {
+                    in_quotes = false;
+                    i += 1;
+                }
+                continue;
+            }
+
+            let len = utf8_len(byte);
+            field.push_str(&csv[i..i + len]);
+            i += len;
+            continue;
+        }
+
+        if byte == QUOTE && field.is_empty() {
+            in_quotes = true;
+            row_started = true;
+            i += 1;
+        } else if byte == delimiter {
+            row.push(std::mem::take(&mut field));
+            row_started = true;
+            i += 1;
+        } else if byte == CR {
+            i += 1;
+        } else if byte == LF {
+            row.push(std::mem::take(&mut field));
+            rows.push(std::mem::take(&mut row));
+            row_started = false;
+            i += 1;
+        } else {
+            let len = utf8_len(byte);
+            field.push_str(&csv[i..i + len]);
+            row_started = true;
+            i += len;
+        }
+    }
+
+    if row_started || !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows
+}
+
+/// Write rows of fields to a CSV document, quoting any field that contains
+/// the delimiter, a quote character, or a newline.
+pub fn write(rows: &[Vec<String>], delimiter: Delimiter) -> String {
+    let delimiter = delimiter.as_byte() as char;
+    let mut csv = String::new();
+
+    for (i, row) in rows.iter().enumerate() {
+        if i > 0 {
+            csv.push('\n');
+        }
+        for (j, field) in row.iter().enumerate() {
+            if j > 0 {
+                csv.push(delimiter);
+            }
+            write_field(&mut csv, field, delimiter);
+        }
+    }
+
+    csv
+}
+
+fn write_field(out: &mut String, field: &str, delimiter: char) {
+    let needs_quoting =
+        field.contains(delimiter) || field.contains('"') || field.contains('\n') || field.contains('\r');
+
+    if !needs_quoting {
+        out.push_str(field);
+        return;
+    }
+
+    out.push('"');
+    for ch in field.chars() {
+        if ch == '"' {
+            out.push('"');
+        }
+        out.push(ch);
+    }
+    out.push('"');
+}
+
+fn utf8_len(byte: u8) -> usize {
+    if byte & 0x80 == 0 {
+        1
+    } else if byte & 0xE0 == 0xC0 {
+        2
+    } else if byte & 0xF0 == 0xE0 {
+        3
+    } else {
+        4
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quoted_field_with_embedded_delimiter_and_newline() {
+        let csv = "label,comment\nok,\"has, a comma\nand a newline\"";
+        let rows = parse(csv, Delimiter::Comma);
+
+        assert_eq!(rows, vec![
+            vec!["label".to_string(), "comment".to_string()],
+            vec!["ok".to_string(), "has, a comma\nand a newline".to_string()],
+        ]);
+    }
+
+    #[test]
+    fn test_parse_escaped_quotes() {
+        let csv = "label\n\"she said \"\"hi\"\"\"";
+        let rows = parse(csv, Delimiter::Comma);
+
+        assert_eq!(rows, vec![
+            vec!["label".to_string()],
+            vec!["she said \"hi\"".to_string()],
+        ]);
+    }
+
+    #[test]
+    fn test_write_quotes_fields_that_need_it() {
+        let rows = vec![vec!["plain".to_string(), "has,comma".to_string(), "has\"quote".to_string()]];
+        let csv = write(&rows, Delimiter::Comma);
+
+        assert_eq!(csv, "plain,\"has,comma\",\"has\"\"quote\"");
+    }
+
+    #[test]
+    fn test_round_trip_through_parse_and_write() {
+        let rows = vec![
+            vec!["Criterion".to_string(), "Selection".to_string(), "Comments".to_string()],
+            vec!["Has a, comma".to_string(), "YES".to_string(), "multi\nline".to_string()],
+        ];
+        let csv = write(&rows, Delimiter::Semicolon);
+        let parsed = parse(&csv, Delimiter::Semicolon);
+
+        assert_eq!(parsed, rows);
+    }
+
+    #[test]
+    fn test_parse_with_non_ascii_field_and_ascii_delimiter() {
+        let csv = "label;comment\noké;café";
+        let rows = parse(csv, Delimiter::Semicolon);
+
+        assert_eq!(rows, vec![
+            vec!["label".to_string(), "comment".to_string()],
+            vec!["oké".to_string(), "café".to_string()],
+        ]);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be an ASCII byte")]
+    fn test_non_ascii_other_delimiter_is_rejected() {
+        Delimiter::Other(0xC3).as_byte();
+    }
+}
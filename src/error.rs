@@ -0,0 +1,112 @@
+//! Structured error types for fallible parsing and selection, so callers
+//! importing user-supplied files (e.g. a GUI) can recover instead of
+//! panicking.
+
+use std::fmt;
+
+/// Error returned by [`Review::try_from_csv`](crate::Review::try_from_csv)
+/// and [`Review::try_from_csv_with_delimiter`](crate::Review::try_from_csv_with_delimiter)
+/// when a CSV scorecard can't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input had no header row.
+    MissingHeader,
+    /// A data row had a different number of columns than the header.
+    RowColumnMismatch {
+        /// 1-indexed data row the mismatch occurred on (the header is row 0).
+        row: usize,
+        /// Number of columns in the header row.
+        expected: usize,
+        /// Number of columns found in this row.
+        found: usize,
+    },
+    /// The input was empty.
+    EmptyInput,
+    /// A criterion had no options with a recognized scoring schema (every
+    /// column parsed as neither `FATAL` nor an integer point value).
+    NoValidOptions {
+        /// Label of the criterion with no valid options.
+        criterion: String,
+    },
+    /// The input wasn't valid JSON, or didn't match the review schema.
+    InvalidJson {
+        /// Description of what went wrong, from the underlying JSON parser.
+        message: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHeader => write!(f, "scorecard is missing a header row"),
+            Self::RowColumnMismatch { row, expected, found } => write!(
+                f,
+                "row {row} has {found} column(s), expected {expected} (matching the header)"
+            ),
+            Self::EmptyInput => write!(f, "scorecard input is empty"),
+            Self::NoValidOptions { criterion } => write!(
+                f,
+                "criterion \"{criterion}\" has no options with a recognized scoring schema"
+            ),
+            Self::InvalidJson { message } => write!(f, "couldn't parse review from JSON: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Error returned by
+/// [`Criterion::try_set_selection_index`](crate::Criterion::try_set_selection_index)
+/// when the given index doesn't correspond to an option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelectionError {
+    /// The out-of-range index that was attempted.
+    pub selection_index: usize,
+    /// The number of options actually available.
+    pub option_count: usize,
+}
+
+impl fmt::Display for SelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "selection index {} is out of range (this criterion has {} option(s))",
+            self.selection_index, self.option_count
+        )
+    }
+}
+
+impl std::error::Error for SelectionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_messages_include_context() {
+        assert_eq!(ParseError::MissingHeader.to_string(), "scorecard is missing a header row");
+        assert_eq!(ParseError::EmptyInput.to_string(), "scorecard input is empty");
+        assert_eq!(
+            ParseError::RowColumnMismatch { row: 2, expected: 3, found: 2 }.to_string(),
+            "row 2 has 2 column(s), expected 3 (matching the header)"
+        );
+        assert_eq!(
+            ParseError::NoValidOptions { criterion: "Criterion 1".to_string() }.to_string(),
+            "criterion \"Criterion 1\" has no options with a recognized scoring schema"
+        );
+        assert_eq!(
+            ParseError::InvalidJson { message: "eof".to_string() }.to_string(),
+            "couldn't parse review from JSON: eof"
+        );
+    }
+
+    #[test]
+    fn test_selection_error_message_includes_context() {
+        let error = SelectionError { selection_index: 5, option_count: 2 };
+
+        assert_eq!(
+            error.to_string(),
+            "selection index 5 is out of range (this criterion has 2 option(s))"
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! Exact fixed-point arithmetic for percent scoring.
+//!
+//! Floating point percentages don't compare deterministically across
+//! platforms and rounding modes. `Fixed` instead stores a percentage as an
+//! integer scaled by [`SCALE`] (hundredths of a percent), so two reviews can
+//! be compared with ordinary integer equality/ordering.
+
+use std::fmt;
+
+/// Scale factor applied to percentages: a `Fixed` value of `6666` represents
+/// `66.66%`.
+const SCALE: i64 = 10_000;
+
+/// A percentage represented as an exact integer scaled by [`SCALE`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Fixed(i64);
+
+impl Fixed {
+    /// The zero percent value, used when a review has no points available to
+    /// score against.
+    pub fn zero() -> Self {
+        Self(0)
+    }
+
+    /// Compute `100 * numerator / denominator` as a `Fixed` percentage,
+    /// rounding half-to-even. Returns [`Fixed::zero`] if `denominator` is 0.
+    pub fn percent_of(numerator: i32, denominator: i32) -> Self {
+        if denominator == 0 {
+            return Self::zero();
+        }
+        Self(div_round_half_to_even(
+            numerator as i64 * SCALE,
+            denominator as i64,
+        ))
+    }
+
+    /// Get the raw scaled integer value backing this `Fixed`.
+    pub fn scaled_value(&self) -> i64 {
+        self.0
+    }
+}
+
+impl fmt::Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let magnitude = self.0.unsigned_abs();
+        let whole = magnitude / (SCALE as u64 / 100);
+        let fraction = magnitude % (SCALE as u64 / 100);
+        write!(f, "{}{}.{:02}%", sign, whole, fraction)
+    }
+}
+
+/// Divide `numerator` by `denominator`, rounding the quotient half-to-even
+/// (banker's rounding) instead of truncating toward zero.
+fn div_round_half_to_even(numerator: i64, denominator: i64) -> i64 {
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+
+    if remainder == 0 {
+        return quotient;
+    }
+
+    let doubled_remainder = remainder.unsigned_abs() * 2;
+    let denominator_abs = denominator.unsigned_abs();
+    let sign = if (numerator < 0) != (denominator < 0) { -1 } else { 1 };
+
+    if doubled_remainder > denominator_abs || (doubled_remainder == denominator_abs && quotient % 2 != 0) {
+        quotient + sign
+    } else {
+        quotient
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_of_formats_two_decimal_places() {
+        assert_eq!(Fixed::percent_of(1, 3).to_string(), "33.33%");
+        assert_eq!(Fixed::percent_of(1, 2).to_string(), "50.00%");
+    }
+
+    #[test]
+    fn test_percent_of_zero_denominator_is_defined_zero() {
+        assert_eq!(Fixed::percent_of(5, 0), Fixed::zero());
+        assert_eq!(Fixed::zero().to_string(), "0.00%");
+    }
+
+    #[test]
+    fn test_round_half_to_even_rounds_ties_to_even_digit() {
+        // 3 * 10_000 / 96 = 312.5 exactly; 312 is already even, stays.
+        assert_eq!(Fixed::percent_of(3, 96).to_string(), "3.12%");
+        // 9 * 10_000 / 96 = 937.5 exactly; 937 is odd, rounds up to 938.
+        assert_eq!(Fixed::percent_of(9, 96).to_string(), "9.38%");
+    }
+
+    #[test]
+    fn test_fixed_values_compare_deterministically() {
+        assert!(Fixed::percent_of(2, 3) > Fixed::percent_of(1, 3));
+        assert_eq!(Fixed::percent_of(1, 3), Fixed::percent_of(1, 3));
+    }
+}
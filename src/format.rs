@@ -0,0 +1,97 @@
+//! Pluggable serialization format for loading and dumping a [`Review`].
+
+use crate::{Delimiter, ParseError, Review};
+
+/// Serialization format used to load or dump a [`Review`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewFormat {
+    /// RFC 4180 CSV using the given field delimiter. Dumping writes each
+    /// criterion's label, selection, and comment, without the scoring
+    /// schema. Loading reads a scorecard (option labels and point values)
+    /// and sets no selections or comments, so a dumped review doesn't load
+    /// back.
+    Csv(Delimiter),
+    /// JSON, via `serde`, losslessly preserving the full review schema
+    /// (criterion options, scoring schema, selections, and comments).
+    Json,
+}
+
+impl Review {
+    /// Load a review from `data` using the given format.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` is malformed. Use [`Review::try_load`] to handle
+    /// this case without panicking.
+    pub fn load(data: &str, format: ReviewFormat) -> Review {
+        match format {
+            ReviewFormat::Csv(delimiter) => Review::from_csv_with_delimiter(data, delimiter),
+            ReviewFormat::Json => Review::from_json(data),
+        }
+    }
+
+    /// Load a review from `data` using the given format, returning a
+    /// [`ParseError`] instead of panicking on malformed input. This is the
+    /// route a GUI should use to import a user-supplied file.
+    pub fn try_load(data: &str, format: ReviewFormat) -> Result<Review, ParseError> {
+        match format {
+            ReviewFormat::Csv(delimiter) => Review::try_from_csv_with_delimiter(data, delimiter),
+            ReviewFormat::Json => Review::try_from_json(data),
+        }
+    }
+
+    /// Dump this review to a string using the given format.
+    pub fn dump(&self, format: ReviewFormat) -> String {
+        match format {
+            ReviewFormat::Csv(delimiter) => self.to_csv_with_delimiter(delimiter),
+            ReviewFormat::Json => self.to_json(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Criterion, CriterionOption, CriterionOptionScore};
+
+    fn sample_review() -> Review {
+        let mut criterion = Criterion::new(
+            "Criterion 1",
+            vec![
+                CriterionOption::new("YES", CriterionOptionScore::Points(3)),
+                CriterionOption::new("NO", CriterionOptionScore::Fatal),
+            ],
+        );
+        criterion.set_selection_index(0);
+        criterion.set_comment("looks good, has a comma");
+        Review::new(vec![criterion])
+    }
+
+    #[test]
+    fn test_json_round_trip_preserves_full_schema() {
+        let review = sample_review();
+
+        let json = review.dump(ReviewFormat::Json);
+        let mut loaded = Review::load(&json, ReviewFormat::Json);
+
+        assert_eq!(loaded.max_points(), review.max_points());
+        assert_eq!(loaded.total_points(), review.total_points());
+        assert_eq!(loaded.criteria_mut()[0].comment(), "looks good, has a comma");
+    }
+
+    #[test]
+    fn test_csv_format_dispatches_to_csv_with_delimiter() {
+        let review = sample_review();
+
+        let csv = review.dump(ReviewFormat::Csv(Delimiter::Semicolon));
+        let loaded = Review::load(&csv, ReviewFormat::Csv(Delimiter::Semicolon));
+
+        assert_eq!(loaded.max_points(), review.max_points());
+    }
+
+    #[test]
+    fn test_try_load_returns_parse_error_instead_of_panicking() {
+        assert!(Review::try_load("not json", ReviewFormat::Json).is_err());
+        assert!(Review::try_load("", ReviewFormat::Csv(Delimiter::Comma)).is_err());
+    }
+}
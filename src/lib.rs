@@ -4,9 +4,23 @@
 //! that can be shared across both the CLI (Command Line Interface) and GUI (Graphical User
 //! Interface) versions of the program.
 
+mod csv;
+mod error;
+mod fixed;
+mod format;
+mod reward;
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+pub use csv::Delimiter;
+pub use error::{ParseError, SelectionError};
+pub use fixed::Fixed;
+pub use format::ReviewFormat;
+pub use reward::{distribute_rewards, rows_to_csv, rows_to_json, RewardRow, RewardWeighting, ReviewerId};
+
 // constants used to parse scorecard from CSV
-const CSV_ROW_DELIMITER: &str = "\n";
-const CSV_COL_DELIMITER: &str = ",";
 const FATAL_STR: &str = "FATAL";
 
 // constants used to export scorecard to CSV
@@ -16,7 +30,8 @@ const COMMENTS_STR: &str = "Comments";
 const SCORE_STR: &str = "Percent Score";
 
 /// Represents the scoring schema associated with a `CriterionOption`.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
 pub enum CriterionOptionScore {
     /// Represents a point value criterion option. When selected, this option's point value is added
     /// to the review's total point value to calculate the score (unless the review contains a
@@ -42,7 +57,7 @@ impl CriterionOptionScore {
 }
 
 /// Represents an option within a `Criterion`, one of which becomes the `selection` during a review.
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CriterionOption {
     /// Label for this criterion option.
     label: String,
@@ -71,8 +86,13 @@ impl CriterionOption {
 }
 
 /// Represents a criterion on which a review is conducted.
-#[derive(Debug)]
-pub struct Criterion {
+///
+/// `D` is the type of external input data used to auto-grade this criterion
+/// via an [`evaluator`](Criterion::set_evaluator). Criteria scored entirely
+/// by hand can ignore `D` and use the default `Criterion` (i.e. `Criterion<()>`).
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Criterion<D = ()> {
     /// Label for this criterion.
     label: String,
     /// Options available for selection in this criterion.
@@ -81,9 +101,24 @@ pub struct Criterion {
     selection_index: Option<usize>,
     /// Optional comment attached to this criterion in the review.
     comment: String,
+    /// Optional evaluator that computes the selection index from input data.
+    #[serde(skip)]
+    evaluator: Option<Box<dyn Fn(&D) -> usize>>,
+}
+
+impl<D> fmt::Debug for Criterion<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Criterion")
+            .field("label", &self.label)
+            .field("options", &self.options)
+            .field("selection_index", &self.selection_index)
+            .field("comment", &self.comment)
+            .field("evaluator", &self.evaluator.as_ref().map(|_| "Fn(&D) -> usize"))
+            .finish()
+    }
 }
 
-impl Criterion {
+impl<D> Criterion<D> {
     /// Create a new criterion.
     pub fn new(label: &str, options: Vec<CriterionOption>) -> Self {
         Self {
@@ -91,16 +126,69 @@ impl Criterion {
             options,
             selection_index: None,
             comment: String::new(),
+            evaluator: None,
+        }
+    }
+
+    /// Attach an evaluator that computes this criterion's selection index
+    /// from external input data, turning a manual check into an automated
+    /// scorecard test. Call [`evaluate`](Criterion::evaluate) to run it.
+    pub fn set_evaluator<F>(&mut self, evaluator: F)
+    where
+        F: Fn(&D) -> usize + 'static,
+    {
+        self.evaluator = Some(Box::new(evaluator));
+    }
+
+    /// Run this criterion's evaluator (if one is attached) against `data`
+    /// and select the option index it returns. Does nothing if no
+    /// evaluator is attached.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the evaluator returns an out-of-range index. Use
+    /// [`try_evaluate`](Criterion::try_evaluate) to handle this case
+    /// without panicking.
+    pub fn evaluate(&mut self, data: &D) {
+        self.try_evaluate(data)
+            .expect("Evaluator returned a nonexistent option index!");
+    }
+
+    /// Run this criterion's evaluator (if one is attached) against `data`
+    /// and select the option index it returns, returning a
+    /// [`SelectionError`] instead of panicking if that index is out of
+    /// range. Does nothing if no evaluator is attached.
+    pub fn try_evaluate(&mut self, data: &D) -> Result<(), SelectionError> {
+        if let Some(evaluator) = &self.evaluator {
+            let selection_index = evaluator(data);
+            self.try_set_selection_index(selection_index)?;
         }
+        Ok(())
     }
 
     /// Set the selection using the index of the option to select.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `selection_index` is out of range. Use
+    /// [`try_set_selection_index`](Criterion::try_set_selection_index) to
+    /// handle this case without panicking.
     pub fn set_selection_index(&mut self, selection_index: usize) {
-        assert!(
-            selection_index < self.options.len(),
-            "Tried to select a nonexistent option!"
-        );
+        self.try_set_selection_index(selection_index)
+            .expect("Tried to select a nonexistent option!");
+    }
+
+    /// Set the selection using the index of the option to select, returning
+    /// a [`SelectionError`] instead of panicking if the index is out of range.
+    pub fn try_set_selection_index(&mut self, selection_index: usize) -> Result<(), SelectionError> {
+        if selection_index >= self.options.len() {
+            return Err(SelectionError {
+                selection_index,
+                option_count: self.options.len(),
+            });
+        }
         self.selection_index = Some(selection_index);
+        Ok(())
     }
 
     /// Get the current selection if one has been made.
@@ -162,23 +250,57 @@ impl Criterion {
 }
 
 /// Represents a QA review in the application
-#[derive(Debug)]
-pub struct Review {
+///
+/// `D` is the type of external input data used to auto-grade criteria that
+/// carry an evaluator; see [`Criterion`]. Reviews scored entirely by hand
+/// can ignore `D` and use the default `Review` (i.e. `Review<()>`).
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Review<D = ()> {
     /// The criteria on which this review is conducted.
-    criteria: Vec<Criterion>,
+    criteria: Vec<Criterion<D>>,
 }
 
-impl Review {
+impl<D> fmt::Debug for Review<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Review").field("criteria", &self.criteria).finish()
+    }
+}
+
+impl<D> Review<D> {
     /// Create a new review.
-    pub fn new(criteria: Vec<Criterion>) -> Self {
+    pub fn new(criteria: Vec<Criterion<D>>) -> Self {
         Self { criteria }
     }
 
     /// Get the review's criteria as a mutable reference.
-    pub fn criteria_mut(&mut self) -> &mut Vec<Criterion> {
+    pub fn criteria_mut(&mut self) -> &mut Vec<Criterion<D>> {
         &mut self.criteria
     }
 
+    /// Run [`Criterion::evaluate`] for every criterion that has an
+    /// evaluator attached, leaving criteria without one untouched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an evaluator returns an out-of-range index. Use
+    /// [`try_evaluate_all`](Review::try_evaluate_all) to handle this case
+    /// without panicking.
+    pub fn evaluate_all(&mut self, data: &D) {
+        self.try_evaluate_all(data)
+            .expect("Evaluator returned a nonexistent option index!");
+    }
+
+    /// Run [`Criterion::try_evaluate`] for every criterion that has an
+    /// evaluator attached, leaving criteria without one untouched, and
+    /// stopping at the first [`SelectionError`] instead of panicking.
+    pub fn try_evaluate_all(&mut self, data: &D) -> Result<(), SelectionError> {
+        for criterion in &mut self.criteria {
+            criterion.try_evaluate(data)?;
+        }
+        Ok(())
+    }
+
     /// Compute the maximum number of points available for this whole review.
     pub fn max_points(&self) -> i32 {
         let x = self
@@ -212,50 +334,92 @@ impl Review {
         total_points
     }
 
-    /// Compute the percent score for this review
-    pub fn percent_score(&self) -> f32 {
-        100 as f32 * self.total_points() as f32 / self.max_points() as f32
+    /// Compute the percent score for this review as an exact fixed-point
+    /// value. Returns [`Fixed::zero`] if `max_points()` is 0 (e.g. a review
+    /// consisting only of fatal options), rather than dividing by zero.
+    pub fn percent_score(&self) -> Fixed {
+        Fixed::percent_of(self.total_points(), self.max_points())
     }
 
+    /// Format the percent score for this review, e.g. `"66.66%"`.
     pub fn percent_score_string(&self) -> String {
-        format!("{:.2}%", self.percent_score())
+        self.percent_score().to_string()
     }
+}
 
-    /// Create a review from a CSV string.
+// CSV and JSON import/export only make sense for reviews scored without an
+// evaluator, so these are implemented for the default `Review` (`Review<()>`)
+// rather than for every `Review<D>`.
+impl Review {
+    /// Create a review from a CSV string, using `,` as the field delimiter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the CSV is malformed. Use [`Review::try_from_csv`] to
+    /// handle this case without panicking.
     pub fn from_csv(csv: &str) -> Review {
-        // remove trailing newline characters from the CSV.
+        Self::from_csv_with_delimiter(csv, Delimiter::Comma)
+    }
+
+    /// Create a review from a CSV string using the given field delimiter.
+    /// Fields may be double-quote-wrapped to contain embedded delimiters,
+    /// newlines, or escaped (`""`) quotes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the CSV is malformed. Use
+    /// [`Review::try_from_csv_with_delimiter`] to handle this case without
+    /// panicking.
+    pub fn from_csv_with_delimiter(csv: &str, delimiter: Delimiter) -> Review {
+        Self::try_from_csv_with_delimiter(csv, delimiter).expect("Couldn't parse scorecard from CSV!")
+    }
+
+    /// Create a review from a CSV string, using `,` as the field delimiter,
+    /// returning a [`ParseError`] instead of panicking on malformed input.
+    pub fn try_from_csv(csv: &str) -> Result<Review, ParseError> {
+        Self::try_from_csv_with_delimiter(csv, Delimiter::Comma)
+    }
+
+    /// Create a review from a CSV string using the given field delimiter,
+    /// returning a [`ParseError`] instead of panicking on malformed input.
+    pub fn try_from_csv_with_delimiter(csv: &str, delimiter: Delimiter) -> Result<Review, ParseError> {
         let csv = csv.trim();
+        if csv.is_empty() {
+            return Err(ParseError::EmptyInput);
+        }
 
-        // split the csv input into lines
-        let lines: Vec<&str> = csv.split(CSV_ROW_DELIMITER).collect();
+        let rows = csv::parse(csv, delimiter);
 
         // get the header row
-        let header: Vec<&str> = lines
-            .get(0)
-            .expect("Couldn't get header row of scorecard!")
-            .split(CSV_COL_DELIMITER)
-            .collect();
+        let header = rows.get(0).ok_or(ParseError::MissingHeader)?;
 
         // create an empty vector to store criteria
         let mut criteria: Vec<Criterion> = Vec::new();
 
-        // iterate through lines in the csv
-        for line in lines
-            .get(1..lines.len())
-            .expect("Couldn't get rows of scorecard!")
-        {
-            // split the line into a row
-            let row: Vec<&str> = line.split(CSV_COL_DELIMITER).collect();
-
+        // iterate through rows in the csv
+        for (row_number, row) in rows.get(1..rows.len()).unwrap_or(&[]).iter().enumerate() {
             // ensure row is correct size
-            assert_eq!(
-                row.len(),
-                header.len(),
-                "Row has the wrong number of columns!"
-            );
+            if row.len() != header.len() {
+                return Err(ParseError::RowColumnMismatch {
+                    row: row_number + 1,
+                    expected: header.len(),
+                    found: row.len(),
+                });
+            }
 
             // get the criterion label
-            let criterion_label = row.get(0).expect("Couldn't get criterion label!");
+            let criterion_label = &row[0];
+
+            // `to_csv` emits a synthetic score-summary row labeled
+            // `SCORE_STR` right after the header; it isn't a real
+            // criterion, so skip it here rather than reporting its empty
+            // option set as `NoValidOptions`. The criterion rows of a
+            // `to_csv` export hold selection labels rather than point
+            // values, so such an export still fails here with
+            // `NoValidOptions`; this parser only reads scorecards.
+            if criterion_label == SCORE_STR {
+                continue;
+            }
 
             // create an empty vec to store criterion options
             let mut criterion_options: Vec<CriterionOption> = Vec::new();
@@ -263,54 +427,99 @@ impl Review {
             // iterate through cells in the column
             for i in 1..row.len() {
                 // get the score for the criterion option
-                let option_score = row.get(i).expect("Couldn't get option score!");
-                match CriterionOptionScore::from_str(&option_score) {
+                let option_score = &row[i];
+                match CriterionOptionScore::from_str(option_score) {
                     Some(option_score) => {
                         // add the criterion option to the options vector
-                        let option_label = header.get(i).expect("Couldn't get option label!");
-                        let option = CriterionOption::new(&option_label, option_score);
+                        let option_label = &header[i];
+                        let option = CriterionOption::new(option_label, option_score);
                         criterion_options.push(option);
                     }
                     None => {}
                 }
             }
+
+            if criterion_options.is_empty() {
+                return Err(ParseError::NoValidOptions {
+                    criterion: criterion_label.clone(),
+                });
+            }
+
             // create the criterion and push it to the criteria vector
-            let criterion = Criterion::new(&criterion_label, criterion_options);
+            let criterion = Criterion::new(criterion_label, criterion_options);
             criteria.push(criterion);
         }
 
         // return the review
-        Self { criteria }
+        Ok(Self { criteria })
     }
 
-    /// Export a review to a CSV string.
+    /// Export a review to a CSV string, using `,` as the field delimiter.
     pub fn to_csv(&self) -> String {
+        self.to_csv_with_delimiter(Delimiter::Comma)
+    }
+
+    /// Export a review to a CSV string using the given field delimiter.
+    /// Fields containing the delimiter, a quote character, or a newline are
+    /// double-quote-wrapped with `""`-escaped quotes.
+    pub fn to_csv_with_delimiter(&self, delimiter: Delimiter) -> String {
         // create an empty mutable vector to store the data
-        let mut data: Vec<Vec<&str>> = Vec::new();
+        let mut data: Vec<Vec<String>> = Vec::new();
 
         // push a header row to the data
-        data.push(vec![CRITERION_STR, SELECTION_STR, COMMENTS_STR]);
+        data.push(vec![
+            CRITERION_STR.to_string(),
+            SELECTION_STR.to_string(),
+            COMMENTS_STR.to_string(),
+        ]);
 
         // push the percentage score to the data
-        let percent_score_string = self.percent_score_string();
-        data.push(vec![SCORE_STR, percent_score_string.as_str(), ""]);
+        data.push(vec![
+            SCORE_STR.to_string(),
+            self.percent_score_string(),
+            String::new(),
+        ]);
 
         // iterate through criteria in the review
         for criterion in &self.criteria {
             // add the label, selection and comment associated with the citerion
             // to the data
-            let label = criterion.label();
+            let label = criterion.label().to_string();
             let selection = match criterion.selection() {
-                Some(option) => option.label(),
-                None => "",
+                Some(option) => option.label().to_string(),
+                None => String::new(),
             };
-            let comment = criterion.comment();
+            let comment = criterion.comment().to_string();
             data.push(vec![label, selection, comment]);
         }
 
-        let rows: Vec<String> = data.iter().map(|row| row.join(CSV_COL_DELIMITER)).collect();
-        let csv: String = rows.join(CSV_ROW_DELIMITER);
-        csv
+        csv::write(&data, delimiter)
+    }
+
+    /// Create a review from a JSON string. Unlike `from_csv`, this losslessly
+    /// restores the full review schema (criterion options, scoring schema,
+    /// selections, and comments), since it round-trips through `serde`
+    /// rather than flattening to labels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the JSON is malformed. Use [`Review::try_from_json`] to
+    /// handle this case without panicking.
+    pub fn from_json(json: &str) -> Review {
+        Self::try_from_json(json).expect("Couldn't parse review from JSON!")
+    }
+
+    /// Create a review from a JSON string, returning a [`ParseError`]
+    /// instead of panicking on malformed input.
+    pub fn try_from_json(json: &str) -> Result<Review, ParseError> {
+        serde_json::from_str(json).map_err(|error| ParseError::InvalidJson {
+            message: error.to_string(),
+        })
+    }
+
+    /// Dump this review to a JSON string, preserving the full review schema.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Couldn't serialize review to JSON!")
     }
 }
 
@@ -319,7 +528,7 @@ impl Review {
 mod tests {
     use std::fs;
 
-    use crate::{Criterion, CriterionOption, CriterionOptionScore, Review};
+    use crate::{Criterion, CriterionOption, CriterionOptionScore, ParseError, Review, SelectionError};
 
     // Test the nax_points and total_points methods on the review are working correctly.
     #[test]
@@ -354,4 +563,108 @@ mod tests {
         assert_eq!(review.criteria.len(), 7);
         assert_eq!(review.max_points(), 6);
     }
+
+    // Test that a review's own `to_csv` output round-trips back through
+    // `from_csv`/`try_from_csv` without choking on the synthetic score row.
+    #[test]
+    fn test_review_csv_round_trip() {
+        let criterion = Criterion::new(
+            "Criterion 1",
+            vec![
+                CriterionOption::new("YES", CriterionOptionScore::Points(3)),
+                CriterionOption::new("NO", CriterionOptionScore::Points(0)),
+            ],
+        );
+        let mut review = Review::new(vec![criterion]);
+        review.criteria_mut()[0].set_selection_index(0);
+
+        let csv = review.to_csv();
+        let round_tripped = Review::try_from_csv(&csv).expect("Failed to round-trip review through CSV!");
+
+        assert_eq!(round_tripped.criteria.len(), 1);
+        assert_eq!(round_tripped.max_points(), 3);
+    }
+
+    // Test that an evaluator returning an out-of-range index surfaces a
+    // SelectionError via try_evaluate instead of panicking.
+    #[test]
+    fn test_criterion_try_evaluate_out_of_range() {
+        let mut criterion = Criterion::new(
+            "Criterion 1",
+            vec![CriterionOption::new("YES", CriterionOptionScore::Points(1))],
+        );
+        criterion.set_evaluator(|_data: &i32| 5);
+
+        let result = criterion.try_evaluate(&0);
+
+        assert!(result.is_err());
+    }
+
+    // Test that Review::evaluate_all drives every criterion with an
+    // evaluator and leaves the rest untouched.
+    #[test]
+    fn test_review_evaluate_all() {
+        let mut with_evaluator = Criterion::new(
+            "Automated",
+            vec![
+                CriterionOption::new("NO", CriterionOptionScore::Points(0)),
+                CriterionOption::new("YES", CriterionOptionScore::Points(1)),
+            ],
+        );
+        with_evaluator.set_evaluator(|data: &i32| if *data > 0 { 1 } else { 0 });
+
+        let manual = Criterion::new(
+            "Manual",
+            vec![CriterionOption::new("YES", CriterionOptionScore::Points(2))],
+        );
+
+        let mut review = Review::new(vec![with_evaluator, manual]);
+        review.evaluate_all(&5);
+
+        assert_eq!(review.criteria_mut()[0].selection().unwrap().label(), "YES");
+        assert_eq!(review.criteria_mut()[1].selection(), None);
+    }
+
+    #[test]
+    fn test_try_from_csv_empty_input() {
+        assert_eq!(Review::try_from_csv("").unwrap_err(), ParseError::EmptyInput);
+    }
+
+    #[test]
+    fn test_try_from_csv_row_column_mismatch() {
+        let csv = "Criterion,YES,NO\nCriterion 1,1,0,0";
+
+        assert_eq!(
+            Review::try_from_csv(csv).unwrap_err(),
+            ParseError::RowColumnMismatch { row: 1, expected: 3, found: 4 }
+        );
+    }
+
+    #[test]
+    fn test_try_from_csv_no_valid_options() {
+        let csv = "Criterion,YES,NO\nCriterion 1,not-a-number,also-not";
+
+        assert_eq!(
+            Review::try_from_csv(csv).unwrap_err(),
+            ParseError::NoValidOptions { criterion: "Criterion 1".to_string() }
+        );
+    }
+
+    #[test]
+    fn test_try_from_json_invalid_json_is_recoverable() {
+        let error = Review::try_from_json("not json").unwrap_err();
+
+        assert!(matches!(error, ParseError::InvalidJson { .. }));
+    }
+
+    #[test]
+    fn test_try_set_selection_index_out_of_range() {
+        let mut criterion: Criterion =
+            Criterion::new("Criterion 1", vec![CriterionOption::new("YES", CriterionOptionScore::Points(1))]);
+
+        assert_eq!(
+            criterion.try_set_selection_index(3).unwrap_err(),
+            SelectionError { selection_index: 3, option_count: 1 }
+        );
+    }
 }
@@ -0,0 +1,241 @@
+//! Multi-reviewer aggregation and proportional reward distribution.
+//!
+//! Given a collection of completed reviews tagged by reviewer, this module
+//! computes per-reviewer aggregate statistics and splits a reward budget
+//! among the reviewers who meet an eligibility bar, analogous to an
+//! advisor-incentive calculation.
+
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use serde::{Deserialize, Serialize};
+
+use crate::csv::{self, Delimiter};
+use crate::Review;
+
+/// Identifies the reviewer who submitted a `Review`.
+pub type ReviewerId = String;
+
+/// How the reward budget is split among eligible reviewers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RewardWeighting {
+    /// Split the budget equally among eligible reviewers.
+    Equal,
+    /// Split the budget proportionally to each reviewer's summed `total_points`.
+    ByTotalPoints,
+}
+
+/// A row in the reward distribution table: one reviewer's aggregate stats
+/// and computed reward.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardRow {
+    /// The reviewer this row describes.
+    pub reviewer_id: ReviewerId,
+    /// The number of reviews this reviewer submitted.
+    pub review_count: usize,
+    /// Whether `review_count` fell within the eligibility range.
+    pub eligible: bool,
+    /// The reward allocated to this reviewer.
+    pub reward: i64,
+}
+
+/// Compute aggregate statistics and a reward distribution across reviewers.
+///
+/// Reviewers whose review count falls within `eligible_range` are
+/// "eligible" and split `total_rewards` according to `weighting`. Integer
+/// remainders from the split are distributed one unit at a time to the
+/// largest-remainder reviewers first (ties broken by input order), so for a
+/// non-negative `total_rewards` and at least one eligible reviewer the
+/// rewards sum exactly to `total_rewards`. If no reviewer is eligible, every
+/// reward is 0; for a negative `total_rewards` the leftover isn't handed out.
+pub fn distribute_rewards(
+    reviews: &[(ReviewerId, Review)],
+    total_rewards: i64,
+    eligible_range: RangeInclusive<usize>,
+    weighting: RewardWeighting,
+) -> Vec<RewardRow> {
+    let mut order: Vec<&ReviewerId> = Vec::new();
+    let mut review_counts: HashMap<&ReviewerId, usize> = HashMap::new();
+    let mut total_points: HashMap<&ReviewerId, i64> = HashMap::new();
+
+    for (reviewer_id, review) in reviews {
+        if !review_counts.contains_key(reviewer_id) {
+            order.push(reviewer_id);
+        }
+        *review_counts.entry(reviewer_id).or_insert(0) += 1;
+        *total_points.entry(reviewer_id).or_insert(0) += review.total_points() as i64;
+    }
+
+    let mut rows: Vec<RewardRow> = order
+        .iter()
+        .map(|id| RewardRow {
+            reviewer_id: (*id).clone(),
+            review_count: review_counts[*id],
+            eligible: eligible_range.contains(&review_counts[*id]),
+            reward: 0,
+        })
+        .collect();
+
+    let weight_of = |id: &ReviewerId| -> i64 {
+        match weighting {
+            RewardWeighting::Equal => 1,
+            RewardWeighting::ByTotalPoints => total_points[id].max(0),
+        }
+    };
+
+    let eligible_count = rows.iter().filter(|row| row.eligible).count() as i64;
+    let mut weight_sum: i64 = rows
+        .iter()
+        .filter(|row| row.eligible)
+        .map(|row| weight_of(&row.reviewer_id))
+        .sum();
+
+    // If weighting by total_points but every eligible reviewer's points sum
+    // to zero or less, weight_sum is 0 and the whole budget would otherwise
+    // go undistributed. Fall back to an equal split so it's still conserved.
+    let equal_fallback = weight_sum == 0 && eligible_count > 0;
+    if equal_fallback {
+        weight_sum = eligible_count;
+    }
+
+    if weight_sum > 0 {
+        let mut distributed = 0_i64;
+        let mut remainders: Vec<(usize, i64)> = Vec::new();
+
+        for (i, row) in rows.iter_mut().enumerate() {
+            if !row.eligible {
+                continue;
+            }
+            let weight = if equal_fallback { 1 } else { weight_of(&row.reviewer_id) };
+            let scaled = total_rewards * weight;
+            row.reward = scaled / weight_sum;
+            distributed += row.reward;
+            remainders.push((i, scaled % weight_sum));
+        }
+
+        let mut leftover = total_rewards - distributed;
+        remainders.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        for (i, _) in remainders {
+            if leftover <= 0 {
+                break;
+            }
+            rows[i].reward += 1;
+            leftover -= 1;
+        }
+    }
+
+    rows
+}
+
+/// Dump a reward distribution table to a CSV string.
+pub fn rows_to_csv(rows: &[RewardRow], delimiter: Delimiter) -> String {
+    let mut data: Vec<Vec<String>> = Vec::with_capacity(rows.len() + 1);
+
+    data.push(vec![
+        "Reviewer ID".to_string(),
+        "Review Count".to_string(),
+        "Eligible".to_string(),
+        "Reward".to_string(),
+    ]);
+
+    for row in rows {
+        data.push(vec![
+            row.reviewer_id.clone(),
+            row.review_count.to_string(),
+            row.eligible.to_string(),
+            row.reward.to_string(),
+        ]);
+    }
+
+    csv::write(&data, delimiter)
+}
+
+/// Dump a reward distribution table to a JSON string.
+pub fn rows_to_json(rows: &[RewardRow]) -> String {
+    serde_json::to_string(rows).expect("Couldn't serialize reward table to JSON!")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Criterion, CriterionOption, CriterionOptionScore};
+
+    fn scored_review(points: i32) -> Review {
+        let mut criterion = Criterion::new(
+            "Criterion 1",
+            vec![
+                CriterionOption::new("YES", CriterionOptionScore::Points(points)),
+                CriterionOption::new("NO", CriterionOptionScore::Points(0)),
+            ],
+        );
+        criterion.set_selection_index(0);
+        Review::new(vec![criterion])
+    }
+
+    #[test]
+    fn test_equal_split_sums_to_total_rewards_with_remainder() {
+        let reviews = vec![
+            ("alice".to_string(), scored_review(1)),
+            ("bob".to_string(), scored_review(1)),
+            ("carol".to_string(), scored_review(1)),
+        ];
+
+        let rows = distribute_rewards(&reviews, 100, 0..=usize::MAX, RewardWeighting::Equal);
+
+        assert_eq!(rows.iter().map(|row| row.reward).sum::<i64>(), 100);
+        assert!(rows.iter().all(|row| row.eligible));
+        // largest-remainder method gives the extra cent to the first ties
+        assert_eq!(rows[0].reward, 34);
+        assert_eq!(rows[1].reward, 33);
+        assert_eq!(rows[2].reward, 33);
+    }
+
+    #[test]
+    fn test_ineligible_reviewers_get_no_reward() {
+        let reviews = vec![
+            ("alice".to_string(), scored_review(1)),
+            ("bob".to_string(), scored_review(1)),
+            ("bob".to_string(), scored_review(1)),
+        ];
+
+        // only reviewers with exactly 1 review are eligible
+        let rows = distribute_rewards(&reviews, 100, 1..=1, RewardWeighting::Equal);
+
+        let alice = rows.iter().find(|row| row.reviewer_id == "alice").unwrap();
+        let bob = rows.iter().find(|row| row.reviewer_id == "bob").unwrap();
+
+        assert!(alice.eligible);
+        assert_eq!(alice.reward, 100);
+        assert!(!bob.eligible);
+        assert_eq!(bob.reward, 0);
+    }
+
+    #[test]
+    fn test_weighted_by_total_points() {
+        let reviews = vec![
+            ("alice".to_string(), scored_review(3)),
+            ("bob".to_string(), scored_review(1)),
+        ];
+
+        let rows = distribute_rewards(&reviews, 100, 0..=usize::MAX, RewardWeighting::ByTotalPoints);
+
+        let alice = rows.iter().find(|row| row.reviewer_id == "alice").unwrap();
+        let bob = rows.iter().find(|row| row.reviewer_id == "bob").unwrap();
+
+        assert_eq!(alice.reward, 75);
+        assert_eq!(bob.reward, 25);
+    }
+
+    #[test]
+    fn test_zero_total_points_falls_back_to_equal_split() {
+        let reviews = vec![
+            ("alice".to_string(), scored_review(0)),
+            ("bob".to_string(), scored_review(0)),
+        ];
+
+        let rows = distribute_rewards(&reviews, 100, 0..=usize::MAX, RewardWeighting::ByTotalPoints);
+
+        assert_eq!(rows.iter().map(|row| row.reward).sum::<i64>(), 100);
+        assert!(rows.iter().all(|row| row.reward == 50));
+    }
+}